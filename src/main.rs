@@ -1,22 +1,31 @@
 use anyhow::{Context, Result};
 use clap::Parser;
+use crossbeam_channel::{unbounded, Sender};
+use dialoguer::MultiSelect;
+use git2::{Repository, Status, StatusOptions};
+use glob::Pattern;
 use indicatif::{ParallelProgressIterator, ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use std::collections::HashSet;
 use std::fs;
 use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 use walkdir::WalkDir;
 
 #[derive(Parser, Debug)]
-#[command(version, about = "Efficiently delete node_modules directories")]
+#[command(version, about = "Efficiently delete node_modules and other build-artifact directories")]
 struct Args {
     /// Directory to start scanning from (default: current)
     #[arg(default_value = ".")]
     dir: PathBuf,
 
+    /// Glob-style directory name to target for deletion (repeatable)
+    #[arg(long, default_value = "node_modules")]
+    target: Vec<String>,
+
     /// Simulate deletion without actually deleting
     #[arg(long)]
     dry_run: bool,
@@ -32,6 +41,82 @@ struct Args {
     /// Paths to exclude (comma-separated)
     #[arg(long, value_delimiter = ',')]
     exclude: Vec<PathBuf>,
+
+    /// Only target directories whose project hasn't been touched in this many days
+    #[arg(long)]
+    older_than: Option<u64>,
+
+    /// Skip targets whose enclosing git repo has uncommitted changes (each skip is reported with --verbose)
+    #[arg(long)]
+    respect_git: bool,
+
+    /// Only target directories that live inside a clean git repository
+    #[arg(long)]
+    only_clean_repos: bool,
+
+    /// Print the N biggest target directories by size (a "whale hunt" view)
+    #[arg(long)]
+    top: Option<usize>,
+
+    /// Only target directories at least this size, e.g. "500MB" or "2GB"
+    #[arg(long)]
+    min_size: Option<String>,
+
+    /// Pick which discovered folders to delete from a checklist, instead of
+    /// an all-or-nothing yes/no prompt. Note: --older-than/--min-size/git
+    /// safety checks are hard filters applied before the checklist is shown,
+    /// so folders they exclude never appear here and can't be re-selected.
+    #[arg(long)]
+    interactive: bool,
+}
+
+/// Git cleanliness of the repository enclosing a candidate directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitStatus {
+    /// No enclosing git repository was found
+    NotARepo,
+    /// Enclosing repo has no uncommitted changes
+    Clean,
+    /// Enclosing repo has uncommitted changes
+    Dirty,
+}
+
+/// A target directory discovered during the scan, along with the metadata
+/// needed to decide whether it's safe to delete.
+#[derive(Debug, Clone)]
+struct ScanEntry {
+    path: PathBuf,
+    kind: String,
+    last_touched: Option<SystemTime>,
+    git_status: GitStatus,
+    /// Directory size in bytes, filled in once during the size-calculation pass.
+    size: u64,
+    /// The directory's own mtime when `size` was computed, used to detect
+    /// whether a re-stat is needed before deletion.
+    mtime_at_scan: Option<SystemTime>,
+}
+
+/// A progress update sent from the scan and deletion worker threads over a
+/// `crossbeam-channel` to the main thread, which renders the scan spinner and
+/// the deletion bar from them. Keeps rendering decoupled from the scan/delete work so the engine
+/// can be driven headless (e.g. under test) without a terminal attached.
+///
+/// The scan phase doesn't know its total up front (it's discovering targets
+/// as it walks), so it reports a running `found` count instead of a `total`.
+#[derive(Debug, Clone, Copy)]
+enum ProgressData {
+    Scanning { dirs_scanned: usize, found: usize },
+    Deleting { processed: usize },
+}
+
+/// Scan-phase settings, bundled so `scan_node_modules` doesn't need a
+/// separate parameter per flag.
+struct ScanOptions {
+    exclude: HashSet<PathBuf>,
+    targets: Vec<Pattern>,
+    check_git: bool,
+    check_stale: bool,
+    verbose: bool,
 }
 
 #[derive(Default, Clone)]
@@ -48,14 +133,135 @@ fn main() -> Result<()> {
     let args = Args::parse();
 
     let exclude: HashSet<PathBuf> = args.exclude.into_iter().collect();
+    let target_patterns: Vec<Pattern> = args
+        .target
+        .iter()
+        .map(|p| Pattern::new(p).with_context(|| format!("Invalid --target pattern: {p}")))
+        .collect::<Result<_>>()?;
+
+    let check_git = args.respect_git || args.only_clean_repos;
+    let check_stale = args.older_than.is_some();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        ctrlc::set_handler(move || {
+            println!("\n🛑 Cancelling... finishing in-flight work and exiting cleanly.");
+            stop.store(true, Ordering::SeqCst);
+        })
+        .context("Failed to install Ctrl-C handler")?;
+    }
 
     // Scan phase
     let scan_start = Instant::now();
-    let node_modules = scan_node_modules(&args.dir, args.depth, &exclude, args.verbose)?;
+    let mut node_modules = {
+        let root = args.dir.clone();
+        let max_depth = args.depth;
+        let scan_options = ScanOptions {
+            exclude: exclude.clone(),
+            targets: target_patterns.clone(),
+            check_git,
+            check_stale,
+            verbose: args.verbose,
+        };
+        let stop = Arc::clone(&stop);
+        let (tx, rx) = unbounded::<ProgressData>();
+
+        let handle =
+            std::thread::spawn(move || scan_node_modules(&root, max_depth, &scan_options, &stop, tx));
+
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(
+            ProgressStyle::default_spinner()
+                .template("{spinner:.green} {msg}")
+                .unwrap()
+                .tick_chars("🔍🔎🔍🔎"),
+        );
+        spinner.set_message("🚀 Scanning for target directories...");
+
+        while let Ok(ProgressData::Scanning { dirs_scanned, found }) = rx.recv() {
+            spinner.set_message(format!(
+                "🔍 Scanning... {} directories searched | 📦 Found: {} targets",
+                dirs_scanned, found
+            ));
+            spinner.tick();
+        }
+
+        let result = handle.join().expect("scan thread panicked")?;
+        spinner.finish_with_message(format!("✅ Scan complete! 📦 Found: {} targets", result.len()));
+        result
+    };
     let scan_duration = scan_start.elapsed();
+    if stop.load(Ordering::SeqCst) {
+        return Ok(());
+    }
 
     if node_modules.is_empty() {
-        println!("🎉 No node_modules directories found! Your disk is already clean! ✨");
+        println!("🎉 No target directories found! Your disk is already clean! ✨");
+        return Ok(());
+    }
+
+    let stale_filter_val = if let Some(days) = args.older_than {
+        let threshold = days
+            .checked_mul(86_400)
+            .and_then(|secs| SystemTime::now().checked_sub(Duration::from_secs(secs)))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let before = node_modules.len();
+        node_modules.retain(|entry| match entry.last_touched {
+            Some(mtime) => mtime < threshold,
+            None => {
+                if args.verbose {
+                    println!("❓ Unknown last-modified time, keeping: {:?}", entry.path);
+                }
+                false
+            }
+        });
+        println!("\n🕰️  Last touched (project files outside the target dir):");
+        for entry in &node_modules {
+            let age = match entry.last_touched {
+                Some(mtime) => match SystemTime::now().duration_since(mtime) {
+                    Ok(age) => format!("{}d ago", age.as_secs() / 86_400),
+                    Err(_) => "just now".to_string(),
+                },
+                None => "unknown".to_string(),
+            };
+            println!("  {:>10}  {:?}", age, entry.path);
+        }
+
+        Some(format!("{} of {} folders (>{}d idle)", node_modules.len(), before, days))
+    } else {
+        None
+    };
+
+    if node_modules.is_empty() {
+        println!("🎉 No target directories are stale enough to qualify! ✨");
+        return Ok(());
+    }
+
+    let git_filter_val = if check_git {
+        let before = node_modules.len();
+        node_modules.retain(|entry| match entry.git_status {
+            GitStatus::Dirty => {
+                if args.verbose {
+                    println!("⚠️  Skipping dirty repo: {:?}", entry.path);
+                }
+                false
+            }
+            GitStatus::Clean => true,
+            GitStatus::NotARepo => {
+                if args.only_clean_repos && args.verbose {
+                    println!("⚠️  Not inside a git repo, skipping: {:?}", entry.path);
+                }
+                !args.only_clean_repos
+            }
+        });
+        Some(format!("{} of {} folders (git-safe)", node_modules.len(), before))
+    } else {
+        None
+    };
+
+    if node_modules.is_empty() {
+        println!("🎉 No target directories passed the git safety check! ✨");
         return Ok(());
     }
 
@@ -68,27 +274,79 @@ fn main() -> Result<()> {
     );
     pb.set_message("📊 Calculating sizes...");
 
-    let total_size: u64 = node_modules
-        .par_iter()
-        .progress_with(pb)
-        .map(|path| calculate_dir_size(path).unwrap_or(0))
-        .sum();
+    node_modules.par_iter_mut().progress_with(pb).for_each(|entry| {
+        entry.mtime_at_scan = entry_mtime(&entry.path);
+        entry.size = calculate_dir_size(&entry.path).unwrap_or(0);
+    });
+    if stop.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    if let Some(top) = args.top {
+        let mut by_size = node_modules.clone();
+        by_size.sort_by_key(|entry| std::cmp::Reverse(entry.size));
+        println!("\n🐋 WHALE HUNT — top {} by size:", top.min(by_size.len()));
+        for (i, entry) in by_size.iter().take(top).enumerate() {
+            println!("  {:>2}. {:>10}  {:?}", i + 1, format_size(entry.size), entry.path);
+        }
+    }
+
+    let min_size_filter_val = if let Some(min_size) = &args.min_size {
+        let threshold = parse_size(min_size)
+            .with_context(|| format!("Invalid --min-size value: {min_size}"))?;
+        let before = node_modules.len();
+        node_modules.retain(|entry| entry.size >= threshold);
+        Some(format!(
+            "{} of {} folders (>={})",
+            node_modules.len(),
+            before,
+            format_size(threshold)
+        ))
+    } else {
+        None
+    };
+
+    if node_modules.is_empty() {
+        println!("🎉 No target directories are big enough to qualify! ✨");
+        return Ok(());
+    }
+
+    let total_size: u64 = node_modules.iter().map(|entry| entry.size).sum();
 
     let size_str = format_size(total_size);
-    
+
+    let mut per_target_counts: std::collections::BTreeMap<String, usize> =
+        std::collections::BTreeMap::new();
+    for entry in &node_modules {
+        *per_target_counts.entry(entry.kind.clone()).or_insert(0) += 1;
+    }
+
     // Professional tabular output with proper alignment
     let duration_val = format!("{:.2}s", scan_duration.as_secs_f64());
     let folders_val = node_modules.len().to_string();
     let size_val = &size_str;
     let savings_val = &size_str;
-    
+
     println!("\n┌─────────────────────────────────────────────────────────────────┐");
     println!("│                        📊 SCAN RESULTS                          │");
     println!("├─────────────────────────────────────────────────────────────────┤");
     println!("│ ⏱️ Scan Duration         │{:>38}│", duration_val);
     println!("│ 📦 Folders Found         │{:>38}│", folders_val);
+    for (kind, count) in &per_target_counts {
+        let label = format!("   └─ {}", kind);
+        println!("│ {:<25}│{:>38}│", label, count);
+    }
     println!("│ 💾 Total Size            │{:>38}│", size_val);
     println!("│ 🎯 Estimated Savings     │{:>38}│", savings_val);
+    if let Some(stale_val) = &stale_filter_val {
+        println!("│ 🕰️  Stale Filter         │{:>38}│", stale_val);
+    }
+    if let Some(git_val) = &git_filter_val {
+        println!("│ 🌳 Git Safety Filter     │{:>38}│", git_val);
+    }
+    if let Some(min_size_val) = &min_size_filter_val {
+        println!("│ 🐋 Min Size Filter       │{:>38}│", min_size_val);
+    }
     println!("└─────────────────────────────────────────────────────────────────┘");
 
     if args.dry_run {
@@ -97,12 +355,45 @@ fn main() -> Result<()> {
     }
 
     // Confirmation
-    print!("\n🚨 NUCLEAR WARNING! Proceed with deletion? (yes/no): ");
-    io::stdout().flush()?;
-    let mut input = String::new();
-    io::stdin().lock().read_line(&mut input)?;
-    if input.trim().to_lowercase() != "yes" {
-        println!("🛡️  Deletion cancelled. Your node_modules live to see another day! 😅");
+    if args.interactive {
+        let items: Vec<String> = node_modules
+            .iter()
+            .map(|entry| format!("{:>10}  {} ({})", format_size(entry.size), entry.path.display(), entry.kind))
+            .collect();
+        // Every entry here already passed the hard filters above
+        // (--older-than/--min-size/git safety), so all start checked.
+        let defaults = vec![true; items.len()];
+
+        let selected = MultiSelect::new()
+            .with_prompt("Select folders to nuke (space to toggle, enter to confirm)")
+            .items(&items)
+            .defaults(&defaults)
+            .interact()
+            .context("Failed to read interactive selection")?;
+
+        if selected.is_empty() {
+            println!("🛡️  Nothing selected. Your node_modules live to see another day! 😅");
+            return Ok(());
+        }
+
+        let selected: HashSet<usize> = selected.into_iter().collect();
+        let mut i = 0;
+        node_modules.retain(|_| {
+            let keep = selected.contains(&i);
+            i += 1;
+            keep
+        });
+    } else {
+        print!("\n🚨 NUCLEAR WARNING! Proceed with deletion? (yes/no): ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().lock().read_line(&mut input)?;
+        if input.trim().to_lowercase() != "yes" {
+            println!("🛡️  Deletion cancelled. Your node_modules live to see another day! 😅");
+            return Ok(());
+        }
+    }
+    if stop.load(Ordering::SeqCst) {
         return Ok(());
     }
 
@@ -115,50 +406,66 @@ fn main() -> Result<()> {
         ..Default::default()
     }));
 
-    let pb = ProgressBar::new(node_modules_len as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template(
-                "💥 {msg} {bar:40.cyan/blue} {pos}/{len} ⏱️ {eta} [{elapsed_precise}] 💾 Freed: {wide_msg}",
-            )?
-            .progress_chars("🚀🌟⭐"),
-    );
-
-    let avg_time_per_folder = Arc::new(Mutex::new(Duration::ZERO));
-    let _start_time = Instant::now();
+    let (progress_tx, progress_rx) = unbounded::<ProgressData>();
 
-    node_modules
-        .into_par_iter()
-        .progress_with(pb.clone())
-        .try_for_each(|path: PathBuf| -> Result<()> {
-            let folder_start = Instant::now();
+    let deletion_handle = {
+        let stats = Arc::clone(&stats);
+        let stop = Arc::clone(&stop);
+        let verbose = args.verbose;
 
-            if args.verbose {
-                println!("🗑️  Processing: {:?}", path);
-            }
+        std::thread::spawn(move || -> Result<()> {
+            node_modules
+                .into_par_iter()
+                .try_for_each(|entry: ScanEntry| -> Result<()> {
+                    if stop.load(Ordering::SeqCst) {
+                        return Ok(()); // Cancelled: leave this folder untouched
+                    }
 
-            let size = calculate_dir_size(&path)?;
-            fs::remove_dir_all(&path).with_context(|| format!("Failed to delete {:?}", path))?;
+                    let path = entry.path;
 
-            let duration = folder_start.elapsed();
-            {
-                let mut stats = stats.lock().unwrap();
-                stats.folders_processed += 1;
-                stats.size_freed += size;
+                    if verbose {
+                        println!("🗑️  Processing: {:?}", path);
+                    }
 
-                let mut avg = avg_time_per_folder.lock().unwrap();
-                *avg = (*avg * (stats.folders_processed as u32 - 1) + duration)
-                    / stats.folders_processed as u32;
+                    // Re-stat only if the folder changed since we sized it;
+                    // otherwise reuse the cached size and skip a second walk.
+                    let size = if entry_mtime(&path) == entry.mtime_at_scan {
+                        entry.size
+                    } else {
+                        calculate_dir_size(&path).unwrap_or(entry.size)
+                    };
+                    fs::remove_dir_all(&path)
+                        .with_context(|| format!("Failed to delete {:?}", path))?;
+
+                    let processed = {
+                        let mut stats = stats.lock().unwrap();
+                        stats.folders_processed += 1;
+                        stats.size_freed += size;
+                        stats.folders_processed
+                    };
+
+                    let _ = progress_tx.send(ProgressData::Deleting { processed });
+
+                    Ok(())
+                })
+        })
+    };
 
-                let remaining = node_modules_len - stats.folders_processed;
-                let eta = *avg * remaining as u32;
+    let pb = ProgressBar::new(node_modules_len as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("💥 {msg} {bar:40.cyan/blue} {pos}/{len} [{elapsed_precise}]")?
+            .progress_chars("🚀🌟⭐"),
+    );
 
-                pb.set_message(format!("💣 Deleting... ETA: {:.2}s", eta.as_secs_f64()));
-                pb.set_message(format!("{}", format_size(stats.size_freed)));
-            }
+    while let Ok(ProgressData::Deleting { processed }) = progress_rx.recv() {
+        pb.set_position(processed as u64);
+        let size_freed = stats.lock().unwrap().size_freed;
+        pb.set_message(format!("Freed: {}", format_size(size_freed)));
+    }
 
-            Ok(())
-        })?;
+    deletion_handle.join().expect("deletion thread panicked")?;
+    pb.finish_with_message("💥 Done");
 
     let deletion_duration = deletion_start.elapsed();
     let stats = stats.lock().unwrap();
@@ -186,98 +493,191 @@ fn main() -> Result<()> {
 fn scan_node_modules(
     root: &Path,
     max_depth: Option<usize>,
-    exclude: &HashSet<PathBuf>,
-    verbose: bool,
-) -> Result<Vec<PathBuf>> {
+    options: &ScanOptions,
+    stop: &AtomicBool,
+    progress_tx: Sender<ProgressData>,
+) -> Result<Vec<ScanEntry>> {
+    let targets = &options.targets;
     let mut node_modules = Vec::new();
     let mut dirs_scanned = 0;
 
-    // Create a spinner for the scanning phase
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap()
-            .tick_chars("🔍🔎🔍🔎"),
-    );
-    spinner.set_message("🚀 Scanning for node_modules directories...");
-
-    let start_time = Instant::now();
-    
     for entry in WalkDir::new(root)
         .max_depth(max_depth.unwrap_or(usize::MAX))
         .into_iter()
         .filter_entry(|e| {
-            // Allow scanning the entry itself, but if it's node_modules, 
-            // don't descend into its children
-            let is_node_modules = e.file_type().is_dir() && e.file_name() == "node_modules";
-            
-            if is_node_modules {
-                // Check if this node_modules is in a parent node_modules directory
-                // by looking at the path components
+            // Allow scanning the entry itself, but if it matches a target
+            // pattern, don't descend into its children
+            let is_target = e.file_type().is_dir() && matches_any_target(e.file_name(), targets);
+
+            if is_target {
+                // Check if this match is nested inside another matched
+                // directory by looking at the path components
                 let path_components: Vec<_> = e.path().components().collect();
                 for component in &path_components[..path_components.len().saturating_sub(1)] {
                     if let std::path::Component::Normal(name) = component {
-                        if *name == "node_modules" {
-                            return false; // Skip if we're inside another node_modules
+                        if matches_any_target(name, targets) {
+                            return false; // Skip if we're inside another matched dir
                         }
                     }
                 }
-                return true; // Allow the node_modules directory itself, but don't descend
+                return true; // Allow the matched directory itself, but don't descend
             }
-            
-            // For non-node_modules directories, check if we're inside a node_modules
+
+            // For non-matching directories, check if we're inside a matched dir
             let path_components: Vec<_> = e.path().components().collect();
             for component in &path_components {
                 if let std::path::Component::Normal(name) = component {
-                    if *name == "node_modules" {
-                        return false; // Skip anything inside node_modules
+                    if matches_any_target(name, targets) {
+                        return false; // Skip anything inside a matched dir
                     }
                 }
             }
-            
+
             true // Allow everything else
         })
         .filter_map(|e| e.ok())
     {
+        if stop.load(Ordering::SeqCst) {
+            break; // Cancelled: return whatever we've found so far
+        }
+
         dirs_scanned += 1;
-        
-        // Update spinner every 50 directories to avoid too frequent updates
+
+        // Report progress every 50 directories to avoid flooding the channel
         if dirs_scanned % 50 == 0 {
-            let elapsed = start_time.elapsed();
-            spinner.set_message(format!(
-                "🔍 Scanning... {} directories searched ({:.1} dirs/sec) | 📦 Found: {} node_modules",
+            let _ = progress_tx.send(ProgressData::Scanning {
                 dirs_scanned,
-                dirs_scanned as f64 / elapsed.as_secs_f64(),
-                node_modules.len()
-            ));
-            spinner.tick();
+                found: node_modules.len(),
+            });
         }
 
-        if entry.file_type().is_dir() && entry.file_name() == "node_modules" {
+        if entry.file_type().is_dir() && matches_any_target(entry.file_name(), targets) {
             let path = entry.path().to_path_buf();
-            if exclude.contains(&path) {
-                if verbose {
+            if options.exclude.contains(&path) {
+                if options.verbose {
                     println!("🚫 Excluding: {:?}", path);
                 }
                 continue;
             }
-            node_modules.push(path);
+            let kind = entry.file_name().to_string_lossy().into_owned();
+            let last_touched = if options.check_stale {
+                project_last_touched(&path)
+            } else {
+                None
+            };
+            let git_status = if options.check_git {
+                git_status_for(&path)
+            } else {
+                GitStatus::NotARepo
+            };
+            node_modules.push(ScanEntry {
+                path,
+                kind,
+                last_touched,
+                git_status,
+                size: 0,
+                mtime_at_scan: None,
+            });
         }
     }
 
-    // Final update and finish spinner
-    let elapsed = start_time.elapsed();
-    spinner.finish_with_message(format!(
-        "✅ Scan complete! {} directories searched in {:.2}s | 📦 Found: {} node_modules",
+    let _ = progress_tx.send(ProgressData::Scanning {
         dirs_scanned,
-        elapsed.as_secs_f64(),
-        node_modules.len()
-    ));
+        found: node_modules.len(),
+    });
+    drop(progress_tx); // Close the channel so the rendering thread's recv loop ends
 
     Ok(node_modules)
 }
 
+fn matches_any_target(name: &std::ffi::OsStr, targets: &[Pattern]) -> bool {
+    let name = name.to_string_lossy();
+    targets.iter().any(|pattern| pattern.matches(&name))
+}
+
+/// Find the newest file modification time anywhere under `node_modules_path`'s
+/// parent project directory, excluding `node_modules_path` itself.
+///
+/// Returns `None` if the age is unknown (unreadable metadata somewhere and no
+/// other file to fall back on), in which case callers should treat the
+/// project as active and keep it. An empty project directory (no files at
+/// all besides `node_modules`) is reported as `SystemTime::UNIX_EPOCH`,
+/// i.e. infinitely old, so it always qualifies for staleness filtering.
+fn project_last_touched(node_modules_path: &Path) -> Option<SystemTime> {
+    let parent = node_modules_path.parent()?;
+
+    let mut newest: Option<SystemTime> = None;
+    let mut saw_unreadable = false;
+
+    for entry in WalkDir::new(parent)
+        .into_iter()
+        .filter_entry(|e| e.path() != node_modules_path)
+        .filter_map(|e| e.ok())
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        match entry.metadata().ok().and_then(|m| m.modified().ok()) {
+            Some(modified) => newest = Some(newest.map_or(modified, |n| n.max(modified))),
+            None => saw_unreadable = true,
+        }
+    }
+
+    match newest {
+        Some(mtime) => Some(mtime),
+        None if saw_unreadable => None,
+        None => Some(SystemTime::UNIX_EPOCH),
+    }
+}
+
+/// Determine whether the git repository enclosing `path` (if any) has
+/// uncommitted changes. Discovery walks up from `path`'s parent, so this
+/// also finds repos rooted above the project directory itself.
+fn git_status_for(path: &Path) -> GitStatus {
+    let parent = match path.parent() {
+        Some(p) => p,
+        None => return GitStatus::NotARepo,
+    };
+
+    let repo = match Repository::discover(parent) {
+        Ok(repo) => repo,
+        Err(_) => return GitStatus::NotARepo,
+    };
+
+    const DIRTY: Status = Status::from_bits_truncate(
+        Status::WT_NEW.bits()
+            | Status::WT_MODIFIED.bits()
+            | Status::WT_DELETED.bits()
+            | Status::WT_RENAMED.bits()
+            | Status::WT_TYPECHANGE.bits()
+            | Status::INDEX_NEW.bits()
+            | Status::INDEX_MODIFIED.bits()
+            | Status::INDEX_DELETED.bits()
+            | Status::INDEX_RENAMED.bits()
+            | Status::INDEX_TYPECHANGE.bits(),
+    );
+
+    let mut status_opts = StatusOptions::new();
+    status_opts.include_untracked(true).recurse_untracked_dirs(true);
+
+    let is_dirty = repo
+        .statuses(Some(&mut status_opts))
+        .map(|statuses| statuses.iter().any(|s| s.status().intersects(DIRTY)))
+        .unwrap_or(false);
+
+    if is_dirty {
+        GitStatus::Dirty
+    } else {
+        GitStatus::Clean
+    }
+}
+
+/// The directory's own last-modified time, used to detect whether a cached
+/// size is still trustworthy.
+fn entry_mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).ok()?.modified().ok()
+}
+
 fn calculate_dir_size(path: &Path) -> Result<u64> {
     let mut total_size = 0u64;
     for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
@@ -306,3 +706,70 @@ fn format_size(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+/// Parse a human-readable size like `"500MB"`, `"2GB"`, or a bare byte count
+/// (`"1024"`) into a byte count. Case-insensitive; the `B` suffix is optional
+/// on the unit (`"2G"` works the same as `"2GB"`).
+fn parse_size(input: &str) -> Result<u64> {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    const TB: f64 = GB * 1024.0;
+
+    let input = input.trim();
+    let split_at = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split_at);
+
+    let number: f64 = number
+        .parse()
+        .with_context(|| format!("Expected a number, got {:?}", number))?;
+
+    let multiplier = match unit.trim().to_uppercase().as_str() {
+        "" | "B" => 1.0,
+        "K" | "KB" => KB,
+        "M" | "MB" => MB,
+        "G" | "GB" => GB,
+        "T" | "TB" => TB,
+        other => anyhow::bail!("Unknown size unit {:?}", other),
+    };
+
+    Ok((number * multiplier).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size_bare_bytes() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+    }
+
+    #[test]
+    fn parse_size_short_and_long_units_agree() {
+        assert_eq!(parse_size("2G").unwrap(), parse_size("2GB").unwrap());
+        assert_eq!(parse_size("2GB").unwrap(), 2 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parse_size_fractional() {
+        assert_eq!(parse_size("1.5GB").unwrap(), (1.5 * 1024.0 * 1024.0 * 1024.0) as u64);
+    }
+
+    #[test]
+    fn parse_size_is_case_insensitive() {
+        assert_eq!(parse_size("500mb").unwrap(), parse_size("500MB").unwrap());
+    }
+
+    #[test]
+    fn parse_size_rejects_unknown_unit() {
+        assert!(parse_size("5XB").is_err());
+    }
+
+    #[test]
+    fn parse_size_rejects_empty_input() {
+        assert!(parse_size("").is_err());
+    }
+}